@@ -0,0 +1,49 @@
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::str::FromStr;
+
+use super::error::Error;
+
+/// Parses the first value of `field`, if present and parseable.
+pub(crate) fn get_one<T>(entry: &HashMap<String, Vec<String>>, field: &str) -> Option<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    match entry.get(field).map(|f| f.get(0).unwrap().parse::<T>()) {
+        Some(result) => match result {
+            Ok(r) => Some(r),
+            Err(_) => None,
+        },
+        None => None,
+    }
+}
+
+/// Parses every value of `field`, defaulting to an empty `Vec` when absent.
+pub(crate) fn get_vec<T>(entry: &HashMap<String, Vec<String>>, field: &str) -> Vec<T>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    match entry.get(field) {
+        Some(v) => v.iter().map(|f| f.parse::<T>().unwrap()).collect(),
+        None => vec![],
+    }
+}
+
+/// Like [`get_one`], but fails with [`Error::MissingAttribute`] instead of silently
+/// omitting the field, for attributes a caller requires to be present.
+pub(crate) fn require_one<T>(
+    entry: &HashMap<String, Vec<String>>,
+    dn: &str,
+    field: &str,
+) -> Result<T, Error>
+where
+    T: FromStr,
+    <T as FromStr>::Err: Debug,
+{
+    get_one(entry, field).ok_or_else(|| Error::MissingAttribute {
+        dn: dn.to_owned(),
+        attr: field.to_owned(),
+    })
+}
@@ -1,36 +1,259 @@
 use async_trait::async_trait;
 use deadpool::managed;
+use ldap3::adapters::{Adapter, EntriesOnly, PagedResults};
 use ldap3::{drive, Ldap, LdapConnAsync, LdapError, Mod, SearchEntry};
 use rand::prelude::SliceRandom;
-use rand::SeedableRng;
-use std::collections::HashSet;
+use rand::{Rng, SeedableRng};
+use regex::Regex;
+use std::collections::{BTreeMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 use trust_dns_resolver::{
     config::{ResolverConfig, ResolverOpts},
     AsyncResolver,
 };
 
+use super::error::Error;
+use super::group::LdapGroup;
 use super::search::SearchAttrs;
-use super::user::{LdapUser, LdapUserChangeSet};
+use super::user::{build_group_regex, LdapUser, LdapUserChangeSet};
 
 type Pool = managed::Pool<LdapManager>;
 
+const DEFAULT_BASE_DN: &str = "dc=csh,dc=rit,dc=edu";
+const DEFAULT_USER_OU: &str = "cn=users,cn=accounts";
+const DEFAULT_GROUP_OU: &str = "cn=groups,cn=accounts";
+const DEFAULT_SRV_DOMAIN: &str = "_ldap._tcp.csh.rit.edu";
+const DEFAULT_POOL_MAX_SIZE: usize = 5;
+const DEFAULT_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Escapes a value for safe interpolation into an LDAP search filter, per RFC 4515.
+///
+/// Replaces `\`, `(`, `)`, `*`, and the NUL byte with their `\XX` hex escapes. Callers
+/// composing their own filters should run any untrusted input through this before
+/// interpolating it, to avoid LDAP filter injection.
+#[must_use]
+pub fn escape_filter(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        match c {
+            '\\' => escaped.push_str("\\5c"),
+            '(' => escaped.push_str("\\28"),
+            ')' => escaped.push_str("\\29"),
+            '*' => escaped.push_str("\\2a"),
+            '\0' => escaped.push_str("\\00"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// Where an `LdapClientBuilder` should find directory servers to connect to.
+#[derive(Clone)]
+enum ServerSource {
+    /// Resolve servers from the SRV record for this domain (e.g. `_ldap._tcp.csh.rit.edu`).
+    Srv(String),
+    /// Connect only to these explicit server URIs, bypassing SRV resolution entirely.
+    Explicit(Vec<String>),
+}
+
+/// Builds an [`LdapClient`] against a configurable directory topology, rather than the
+/// hardcoded `dc=csh,dc=rit,dc=edu` tree. Useful for pointing the client at a local
+/// slapd/lldap instance in tests or CI.
+#[derive(Clone)]
+pub struct LdapClientBuilder {
+    base_dn: String,
+    user_ou: String,
+    group_ou: String,
+    servers: ServerSource,
+    pool_max_size: usize,
+    timeout: Duration,
+}
+
+impl Default for LdapClientBuilder {
+    fn default() -> Self {
+        LdapClientBuilder {
+            base_dn: DEFAULT_BASE_DN.to_owned(),
+            user_ou: DEFAULT_USER_OU.to_owned(),
+            group_ou: DEFAULT_GROUP_OU.to_owned(),
+            servers: ServerSource::Srv(DEFAULT_SRV_DOMAIN.to_owned()),
+            pool_max_size: DEFAULT_POOL_MAX_SIZE,
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+impl LdapClientBuilder {
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the root DN of the directory (default `dc=csh,dc=rit,dc=edu`).
+    #[must_use]
+    pub fn base_dn(mut self, base_dn: impl Into<String>) -> Self {
+        self.base_dn = base_dn.into();
+        self
+    }
+
+    /// Sets the RDN path to the users subtree, relative to `base_dn` (default
+    /// `cn=users,cn=accounts`); combined with `base_dn` to form the user search base.
+    #[must_use]
+    pub fn user_ou(mut self, user_ou: impl Into<String>) -> Self {
+        self.user_ou = user_ou.into();
+        self
+    }
+
+    /// Sets the RDN path to the groups subtree (default `cn=groups,cn=accounts`);
+    /// combined with `base_dn` to form the group search base.
+    #[must_use]
+    pub fn group_ou(mut self, group_ou: impl Into<String>) -> Self {
+        self.group_ou = group_ou.into();
+        self
+    }
+
+    /// Resolves directory servers from the SRV record for `domain` (default
+    /// `_ldap._tcp.csh.rit.edu`). Overrides any previous call to `servers`.
+    #[must_use]
+    pub fn srv_domain(mut self, domain: impl Into<String>) -> Self {
+        self.servers = ServerSource::Srv(domain.into());
+        self
+    }
+
+    /// Connects only to these explicit server URIs, bypassing SRV resolution. Overrides
+    /// any previous call to `srv_domain`.
+    #[must_use]
+    pub fn servers(mut self, servers: Vec<String>) -> Self {
+        self.servers = ServerSource::Explicit(servers);
+        self
+    }
+
+    /// Sets the maximum number of pooled connections (default 5).
+    #[must_use]
+    pub fn pool_max_size(mut self, pool_max_size: usize) -> Self {
+        self.pool_max_size = pool_max_size;
+        self
+    }
+
+    /// Sets the timeout for user and group lookup searches (default 5 seconds). The
+    /// searches of `get_all_users` and `group_members` run without this timeout.
+    #[must_use]
+    pub fn timeout(mut self, timeout: Duration) -> Self {
+        self.timeout = timeout;
+        self
+    }
+
+    pub async fn build(self, bind_dn: &str, bind_pw: &str) -> Result<LdapClient, Error> {
+        let ldap_servers = match self.servers {
+            ServerSource::Srv(domain) => resolve_srv_records(&domain).await?,
+            ServerSource::Explicit(servers) => servers
+                .into_iter()
+                .map(|target| SrvRecord {
+                    target,
+                    priority: 0,
+                    weight: 0,
+                })
+                .collect(),
+        };
+
+        let ldap_manager = LdapManager::new(ldap_servers, bind_dn, bind_pw);
+        let ldap_pool = Pool::builder(ldap_manager)
+            .max_size(self.pool_max_size)
+            .build()?;
+
+        let group_base = format!("{},{}", self.group_ou, self.base_dn);
+        let group_regex = build_group_regex(&group_base);
+
+        Ok(LdapClient {
+            ldap: Arc::new(ldap_pool),
+            user_base: format!("{},{}", self.user_ou, self.base_dn),
+            group_base,
+            group_regex,
+            timeout: self.timeout,
+        })
+    }
+}
+
 #[derive(Clone)]
 pub struct LdapClient {
     ldap: Arc<Pool>,
+    user_base: String,
+    group_base: String,
+    group_regex: Regex,
+    timeout: Duration,
+}
+
+/// A directory server candidate, as returned by SRV resolution (or synthesized with
+/// priority/weight 0 for an explicit server list).
+#[derive(Clone)]
+struct SrvRecord {
+    target: String,
+    priority: u16,
+    weight: u16,
+}
+
+/// Orders `records` per RFC 2782: ascending priority, then a weighted random draw
+/// within each priority tier. Zero-weight records are still eligible, but are tried
+/// only after every nonzero-weight record in their tier has been exhausted.
+fn order_by_priority_weight(records: &[SrvRecord]) -> Vec<String> {
+    let mut by_priority: BTreeMap<u16, Vec<SrvRecord>> = BTreeMap::new();
+    for record in records {
+        by_priority
+            .entry(record.priority)
+            .or_default()
+            .push(record.clone());
+    }
+
+    let mut rng = rand::rngs::StdRng::from_entropy();
+    let mut ordered = Vec::with_capacity(records.len());
+    for (_priority, tier) in by_priority {
+        let (mut zero_weight, mut weighted): (Vec<_>, Vec<_>) =
+            tier.into_iter().partition(|r| r.weight == 0);
+
+        while !weighted.is_empty() {
+            let total_weight: u32 = weighted.iter().map(|r| u32::from(r.weight)).sum();
+            let mut pick = rng.gen_range(0..total_weight);
+            let chosen = weighted
+                .iter()
+                .position(|r| match pick.checked_sub(u32::from(r.weight)) {
+                    Some(remainder) => {
+                        pick = remainder;
+                        false
+                    }
+                    None => true,
+                })
+                .unwrap_or(0);
+            ordered.push(weighted.remove(chosen).target);
+        }
+
+        zero_weight.shuffle(&mut rng);
+        ordered.extend(zero_weight.into_iter().map(|r| r.target));
+    }
+
+    ordered
+}
+
+/// Attempts to connect to and bind against a single server, verifying liveness with a
+/// `WhoAmI` exop before handing the connection back (the same probe `recycle` uses).
+async fn connect(server: &str, bind_dn: &str, bind_pw: &str) -> Result<Ldap, LdapError> {
+    let (conn, mut ldap) = LdapConnAsync::new(server).await?;
+    drive!(conn);
+
+    ldap.simple_bind(bind_dn, bind_pw).await?.success()?;
+    ldap.extended(ldap3::exop::WhoAmI).await?.success()?;
+
+    Ok(ldap)
 }
 
 #[derive(Clone)]
 struct LdapManager {
-    ldap_servers: Vec<String>,
+    ldap_servers: Vec<SrvRecord>,
     bind_dn: String,
     bind_pw: String,
 }
 
 impl LdapManager {
-    pub async fn new(bind_dn: &str, bind_pw: &str) -> Self {
-        let ldap_servers = get_ldap_servers().await;
-
+    pub fn new(ldap_servers: Vec<SrvRecord>, bind_dn: &str, bind_pw: &str) -> Self {
         LdapManager {
             ldap_servers,
             bind_dn: bind_dn.to_owned(),
@@ -45,20 +268,24 @@ impl managed::Manager for LdapManager {
     type Error = LdapError;
 
     async fn create(&self) -> Result<Self::Type, Self::Error> {
-        let (conn, mut ldap) = LdapConnAsync::new(
-            self.ldap_servers
-                .choose(&mut rand::rngs::StdRng::from_entropy())
-                .unwrap(),
-        )
-        .await
-        .unwrap();
-        drive!(conn);
-
-        ldap.simple_bind(&self.bind_dn, &self.bind_pw)
-            .await
-            .unwrap();
+        let ordered_servers = order_by_priority_weight(&self.ldap_servers);
+
+        if ordered_servers.is_empty() {
+            return Err(LdapError::Io(std::io::Error::new(
+                std::io::ErrorKind::NotConnected,
+                "LdapManager must be configured with at least one server",
+            )));
+        }
+
+        let mut last_err = None;
+        for server in &ordered_servers {
+            match connect(server, &self.bind_dn, &self.bind_pw).await {
+                Ok(ldap) => return Ok(ldap),
+                Err(e) => last_err = Some(e),
+            }
+        }
 
-        Ok(ldap)
+        Err(last_err.expect("ordered_servers is non-empty, so the loop sets last_err on every path that doesn't early-return"))
     }
 
     async fn recycle(&self, ldap: &mut Self::Type) -> managed::RecycleResult<Self::Error> {
@@ -68,136 +295,264 @@ impl managed::Manager for LdapManager {
 }
 
 impl LdapClient {
-    pub async fn new(bind_dn: &str, bind_pw: &str) -> Self {
-        let ldap_manager = LdapManager::new(bind_dn, bind_pw).await;
-        let ldap_pool = Pool::builder(ldap_manager).max_size(5).build().unwrap();
-
-        LdapClient {
-            ldap: Arc::new(ldap_pool),
-        }
+    pub async fn new(bind_dn: &str, bind_pw: &str) -> Result<Self, Error> {
+        LdapClientBuilder::new().build(bind_dn, bind_pw).await
     }
 
-    pub async fn search_users(&mut self, query: &str) -> Vec<LdapUser> {
-        let mut ldap = self.ldap.get().await.unwrap();
-        ldap.with_timeout(std::time::Duration::from_secs(5));
+    pub async fn search_users(&mut self, query: &str) -> Result<Vec<LdapUser>, Error> {
+        let mut ldap = self.ldap.get().await?;
+        ldap.with_timeout(self.timeout);
         let (results, _result) = ldap
             .search(
-                "cn=users,cn=accounts,dc=csh,dc=rit,dc=edu",
+                &self.user_base,
                 ldap3::Scope::Subtree,
-                &format!("(|(uid=*{query}*)(cn=*{query}*))"),
+                &{
+                    let query = escape_filter(query);
+                    format!("(|(uid=*{query}*)(cn=*{query}*))")
+                },
                 SearchAttrs::default().finalize(),
             )
-            .await
-            .unwrap()
-            .success()
-            .unwrap();
+            .await?
+            .success()?;
 
         results
             .iter()
             .map(|result| {
                 let user = SearchEntry::construct(result.to_owned());
-                LdapUser::from_entry(&user)
+                LdapUser::try_from_entry(&user, &self.group_regex)
             })
             .collect()
     }
 
-    pub async fn _do_not_use_get_all_users(&mut self) -> Vec<LdapUser> {
-        let mut ldap = self.ldap.get().await.unwrap();
-
-        let (results, _result) = ldap
-            .search(
-                "cn=users,cn=accounts,dc=csh,dc=rit,dc=edu",
+    /// Enumerates every user under the configured user search base, paging through the
+    /// directory with the RFC 2696 Simple Paged Results control instead of issuing a
+    /// single unbounded subtree search.
+    ///
+    /// `filter` overrides the default `(objectClass=cshMember)` filter, letting callers
+    /// enumerate a subset. `page_size` controls how many entries are requested per page
+    /// (defaults to 500 when `None`).
+    pub async fn get_all_users(
+        &mut self,
+        filter: Option<&str>,
+        page_size: Option<i32>,
+    ) -> Result<Vec<LdapUser>, Error> {
+        let mut ldap = self.ldap.get().await?;
+
+        let adapters: Vec<Box<dyn Adapter<_, _>>> = vec![
+            Box::new(EntriesOnly::new()),
+            Box::new(PagedResults::new(page_size.unwrap_or(500))),
+        ];
+        let mut search = ldap
+            .streaming_search_with(
+                adapters,
+                &self.user_base,
                 ldap3::Scope::Subtree,
-                "(objectClass=cshMember)",
+                filter.unwrap_or("(objectClass=cshMember)"),
                 SearchAttrs::default().finalize(),
             )
             .await
-            .unwrap()
-            .success()
-            .unwrap();
+            .map_err(Error::from)?;
 
-        results
-            .iter()
-            .map(|result| {
-                let user = SearchEntry::construct(result.clone());
-                LdapUser::from_entry(&user)
-            })
-            .collect()
+        let mut users = Vec::new();
+        while let Some(result) = search.next().await.map_err(Error::from)? {
+            let user = SearchEntry::construct(result);
+            users.push(LdapUser::try_from_entry(&user, &self.group_regex)?);
+        }
+        search.finish().await.success().map_err(Error::from)?;
+
+        Ok(users)
     }
 
-    pub async fn get_user(&mut self, uid: &str) -> Option<LdapUser> {
-        let mut ldap = self.ldap.get().await.unwrap();
+    pub async fn get_user(&mut self, uid: &str) -> Result<Option<LdapUser>, Error> {
+        let mut ldap = self.ldap.get().await?;
 
-        ldap.with_timeout(std::time::Duration::from_secs(5));
+        ldap.with_timeout(self.timeout);
         let (results, _result) = ldap
             .search(
-                "cn=users,cn=accounts,dc=csh,dc=rit,dc=edu",
+                &self.user_base,
                 ldap3::Scope::Subtree,
-                &format!("uid={uid}"),
+                &format!("uid={}", escape_filter(uid)),
                 SearchAttrs::default().finalize(),
             )
-            .await
-            .unwrap()
-            .success()
-            .unwrap();
+            .await?
+            .success()?;
 
         if results.len() == 1 {
             let user = SearchEntry::construct(results.get(0).unwrap().to_owned());
-            Some(LdapUser::from_entry(&user))
+            Ok(Some(LdapUser::try_from_entry(&user, &self.group_regex)?))
         } else {
-            None
+            Ok(None)
         }
     }
 
-    pub async fn get_user_by_ibutton(&mut self, ibutton: &str) -> Option<LdapUser> {
-        let mut ldap = self.ldap.get().await.unwrap();
+    pub async fn get_user_by_ibutton(&mut self, ibutton: &str) -> Result<Option<LdapUser>, Error> {
+        let mut ldap = self.ldap.get().await?;
 
-        ldap.with_timeout(std::time::Duration::from_secs(5));
+        ldap.with_timeout(self.timeout);
         let (results, _result) = ldap
             .search(
-                "cn=users,cn=accounts,dc=csh,dc=rit,dc=edu",
+                &self.user_base,
                 ldap3::Scope::Subtree,
-                &format!("ibutton={ibutton}"),
+                &format!("ibutton={}", escape_filter(ibutton)),
                 SearchAttrs::default().finalize(),
             )
-            .await
-            .unwrap()
-            .success()
-            .unwrap();
+            .await?
+            .success()?;
 
         if results.len() == 1 {
             let user = SearchEntry::construct(results.get(0).unwrap().to_owned());
-            Some(LdapUser::from_entry(&user))
+            Ok(Some(LdapUser::try_from_entry(&user, &self.group_regex)?))
         } else {
-            None
+            Ok(None)
         }
     }
 
-    pub async fn get_user_by_phone(&mut self, phone: &str) -> Option<LdapUser> {
-        let mut ldap = self.ldap.get().await.unwrap();
-        ldap.with_timeout(std::time::Duration::from_secs(5));
+    pub async fn get_user_by_phone(&mut self, phone: &str) -> Result<Option<LdapUser>, Error> {
+        let mut ldap = self.ldap.get().await?;
+        ldap.with_timeout(self.timeout);
         let (results, _result) = ldap
             .search(
-                "cn=users,cn=accounts,dc=csh,dc=rit,dc=edu",
+                &self.user_base,
                 ldap3::Scope::Subtree,
-                &format!("mobile={phone}"),
+                &format!("mobile={}", escape_filter(phone)),
                 SearchAttrs::default().finalize(),
             )
-            .await
-            .unwrap()
-            .success()
-            .unwrap();
+            .await?
+            .success()?;
 
         if results.len() == 1 {
             let user = SearchEntry::construct(results.get(0).unwrap().to_owned());
-            Some(LdapUser::from_entry(&user))
+            Ok(Some(LdapUser::try_from_entry(&user, &self.group_regex)?))
+        } else {
+            Ok(None)
+        }
+    }
+
+    pub async fn get_group(&mut self, cn: &str) -> Result<Option<LdapGroup>, Error> {
+        let mut ldap = self.ldap.get().await?;
+
+        ldap.with_timeout(self.timeout);
+        let (results, _result) = ldap
+            .search(
+                &self.group_base,
+                ldap3::Scope::Subtree,
+                &format!("cn={}", escape_filter(cn)),
+                SearchAttrs::default().finalize(),
+            )
+            .await?
+            .success()?;
+
+        if results.len() == 1 {
+            let group = SearchEntry::construct(results.get(0).unwrap().to_owned());
+            Ok(Some(LdapGroup::try_from_entry(&group)?))
         } else {
-            None
+            Ok(None)
+        }
+    }
+
+    pub async fn search_groups(&mut self, query: &str) -> Result<Vec<LdapGroup>, Error> {
+        let mut ldap = self.ldap.get().await?;
+        ldap.with_timeout(self.timeout);
+        let (results, _result) = ldap
+            .search(
+                &self.group_base,
+                ldap3::Scope::Subtree,
+                &format!("cn=*{}*", escape_filter(query)),
+                SearchAttrs::default().finalize(),
+            )
+            .await?
+            .success()?;
+
+        results
+            .iter()
+            .map(|result| {
+                let group = SearchEntry::construct(result.to_owned());
+                LdapGroup::try_from_entry(&group)
+            })
+            .collect()
+    }
+
+    pub async fn group_members(&mut self, cn: &str) -> Result<Vec<LdapUser>, Error> {
+        let group = match self.get_group(cn).await? {
+            Some(group) => group,
+            None => return Ok(vec![]),
+        };
+
+        let mut ldap = self.ldap.get().await?;
+        let mut users = Vec::new();
+        for member_dn in &group.member {
+            let (results, _result) = ldap
+                .search(
+                    member_dn,
+                    ldap3::Scope::Base,
+                    "(objectClass=cshMember)",
+                    SearchAttrs::default().finalize(),
+                )
+                .await?
+                .success()?;
+
+            if let Some(result) = results.into_iter().next() {
+                let user = SearchEntry::construct(result);
+                users.push(LdapUser::try_from_entry(&user, &self.group_regex)?);
+            }
         }
+
+        Ok(users)
+    }
+
+    pub async fn add_member(&mut self, group_cn: &str, user_dn: &str) -> Result<(), Error> {
+        let group = match self.get_group(group_cn).await? {
+            Some(group) => group,
+            None => return Err(Error::GroupNotFound(group_cn.to_owned())),
+        };
+
+        let mut ldap = self.ldap.get().await?;
+        let changes = vec![Mod::Add(
+            String::from("member"),
+            HashSet::from([user_dn.to_owned()]),
+        )];
+
+        ldap.modify(&group.dn, changes).await?;
+        Ok(())
+    }
+
+    pub async fn remove_member(&mut self, group_cn: &str, user_dn: &str) -> Result<(), Error> {
+        let group = match self.get_group(group_cn).await? {
+            Some(group) => group,
+            None => return Err(Error::GroupNotFound(group_cn.to_owned())),
+        };
+
+        let mut ldap = self.ldap.get().await?;
+        let changes = vec![Mod::Delete(
+            String::from("member"),
+            HashSet::from([user_dn.to_owned()]),
+        )];
+
+        ldap.modify(&group.dn, changes).await?;
+        Ok(())
+    }
+
+    /// Sets (or resets) a user's password via the RFC 3062 Password Modify extended
+    /// operation. If `new_pw` is `None`, the server generates a password and returns it.
+    pub async fn change_password(
+        &mut self,
+        dn: &str,
+        new_pw: Option<&str>,
+        old_pw: Option<&str>,
+    ) -> Result<Option<String>, Error> {
+        let mut ldap = self.ldap.get().await?;
+
+        let (exop, _result) = ldap
+            .extended(ldap3::exop::PasswordModify::new(Some(dn), old_pw, new_pw))
+            .await?
+            .success()?;
+
+        let response = ldap3::exop::PasswordModifyResp::from(exop);
+        Ok(response.generated_password)
     }
 
-    pub async fn update_user(&mut self, change_set: &LdapUserChangeSet) {
-        let mut ldap = self.ldap.get().await.unwrap();
+    pub async fn update_user(&mut self, change_set: &LdapUserChangeSet) -> Result<(), Error> {
+        let mut ldap = self.ldap.get().await?;
 
         let mut changes = Vec::new();
         if change_set.drinkBalance.is_some() {
@@ -213,21 +568,19 @@ impl LdapClient {
             ));
         }
 
-        match ldap.modify(&change_set.dn, changes).await {
-            Ok(_) => {}
-            Err(e) => eprintln!("{:#?}", e),
-        }
+        ldap.modify(&change_set.dn, changes).await?;
+        Ok(())
     }
 
-    pub async fn deactivate_user(&mut self, dn: &str) {
+    pub async fn deactivate_user(&mut self, dn: &str) -> Result<(), Error> {
         self.set_user_nslock(dn, true).await
     }
-    pub async fn activate_user(&mut self, dn: &str) {
+    pub async fn activate_user(&mut self, dn: &str) -> Result<(), Error> {
         self.set_user_nslock(dn, false).await
     }
 
-    async fn set_user_nslock(&mut self, dn: &str, locked: bool) {
-        let mut ldap = self.ldap.get().await.unwrap();
+    async fn set_user_nslock(&mut self, dn: &str, locked: bool) -> Result<(), Error> {
+        let mut ldap = self.ldap.get().await?;
 
         let mut changes = Vec::new();
         changes.push(Mod::Replace(
@@ -235,26 +588,77 @@ impl LdapClient {
             HashSet::from([locked.to_string()]),
         ));
 
-        match ldap.modify(dn, changes).await {
-            Ok(_) => {}
-            Err(e) => eprintln!("{:#?}", e),
-        }
+        ldap.modify(dn, changes).await?;
+        Ok(())
     }
 }
 
-async fn get_ldap_servers() -> Vec<String> {
-    let resolver =
-        AsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default()).unwrap();
-    let response = resolver.srv_lookup("_ldap._tcp.csh.rit.edu").await.unwrap();
+async fn resolve_srv_records(srv_domain: &str) -> Result<Vec<SrvRecord>, Error> {
+    let resolver = AsyncResolver::tokio(ResolverConfig::default(), ResolverOpts::default())?;
+    let response = resolver.srv_lookup(srv_domain).await?;
 
-    // TODO: Make sure servers are working
-    response
+    Ok(response
         .iter()
-        .map(|record| {
-            format!(
+        .map(|record| SrvRecord {
+            target: format!(
                 "ldaps://{}",
                 record.target().to_string().trim_end_matches('.')
-            )
+            ),
+            priority: record.priority(),
+            weight: record.weight(),
         })
-        .collect()
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn escape_filter_escapes_special_characters() {
+        assert_eq!(escape_filter("*)(uid=admin"), "\\2a\\29\\28uid=admin");
+    }
+
+    #[test]
+    fn escape_filter_leaves_plain_input_untouched() {
+        assert_eq!(escape_filter("jdoe"), "jdoe");
+    }
+
+    #[test]
+    fn order_by_priority_weight_orders_lower_priority_tiers_first() {
+        let records = vec![
+            SrvRecord {
+                target: "high".to_owned(),
+                priority: 10,
+                weight: 1,
+            },
+            SrvRecord {
+                target: "low".to_owned(),
+                priority: 0,
+                weight: 1,
+            },
+        ];
+
+        let ordered = order_by_priority_weight(&records);
+        assert_eq!(ordered, vec!["low".to_owned(), "high".to_owned()]);
+    }
+
+    #[test]
+    fn order_by_priority_weight_tries_zero_weight_records_last_within_a_tier() {
+        let records = vec![
+            SrvRecord {
+                target: "zero".to_owned(),
+                priority: 0,
+                weight: 0,
+            },
+            SrvRecord {
+                target: "weighted".to_owned(),
+                priority: 0,
+                weight: 1,
+            },
+        ];
+
+        let ordered = order_by_priority_weight(&records);
+        assert_eq!(ordered, vec!["weighted".to_owned(), "zero".to_owned()]);
+    }
 }
@@ -0,0 +1,73 @@
+use deadpool::managed::{BuildError, PoolError};
+use ldap3::LdapError;
+use std::fmt;
+use trust_dns_resolver::error::ResolveError;
+
+/// The error type returned by every fallible `LdapClient` operation.
+#[derive(Debug)]
+pub enum Error {
+    /// The underlying LDAP operation (bind, search, modify, extended op, ...) failed.
+    Ldap(LdapError),
+    /// Checking out a connection from the pool failed.
+    Pool(PoolError<LdapError>),
+    /// Constructing the connection pool itself failed.
+    PoolBuild(BuildError),
+    /// Resolving the directory's SRV record failed.
+    Dns(ResolveError),
+    /// A directory entry was missing an attribute the caller required.
+    MissingAttribute { dn: String, attr: String },
+    /// A group lookup by `cn` (e.g. for `add_member`/`remove_member`) found no match.
+    GroupNotFound(String),
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Ldap(e) => write!(f, "LDAP error: {e}"),
+            Error::Pool(e) => write!(f, "connection pool error: {e}"),
+            Error::PoolBuild(e) => write!(f, "failed to build connection pool: {e}"),
+            Error::Dns(e) => write!(f, "SRV resolution error: {e}"),
+            Error::MissingAttribute { dn, attr } => {
+                write!(f, "entry '{dn}' is missing required attribute '{attr}'")
+            }
+            Error::GroupNotFound(cn) => write!(f, "no group found with cn '{cn}'"),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Ldap(e) => Some(e),
+            Error::Pool(e) => Some(e),
+            Error::PoolBuild(e) => Some(e),
+            Error::Dns(e) => Some(e),
+            Error::MissingAttribute { .. } => None,
+            Error::GroupNotFound(_) => None,
+        }
+    }
+}
+
+impl From<LdapError> for Error {
+    fn from(e: LdapError) -> Self {
+        Error::Ldap(e)
+    }
+}
+
+impl From<PoolError<LdapError>> for Error {
+    fn from(e: PoolError<LdapError>) -> Self {
+        Error::Pool(e)
+    }
+}
+
+impl From<ResolveError> for Error {
+    fn from(e: ResolveError) -> Self {
+        Error::Dns(e)
+    }
+}
+
+impl From<BuildError> for Error {
+    fn from(e: BuildError) -> Self {
+        Error::PoolBuild(e)
+    }
+}
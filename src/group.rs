@@ -0,0 +1,30 @@
+use ldap3::SearchEntry;
+use serde::{Deserialize, Serialize};
+
+use super::attrs::{get_one, get_vec, require_one};
+use super::error::Error;
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+#[allow(non_snake_case)]
+pub struct LdapGroup {
+    pub dn: String,
+    pub cn: String,
+    pub member: Vec<String>,
+    pub gidNumber: Option<i64>,
+}
+
+impl LdapGroup {
+    /// Builds an `LdapGroup` from a raw search entry.
+    ///
+    /// Fails with [`Error::MissingAttribute`] if the entry lacks `cn`, rather than
+    /// panicking.
+    pub fn try_from_entry(entry: &SearchEntry) -> Result<Self, Error> {
+        let group_attrs = &entry.attrs;
+        Ok(LdapGroup {
+            dn: entry.dn.clone(),
+            cn: require_one(group_attrs, &entry.dn, "cn")?,
+            member: get_vec(group_attrs, "member"),
+            gidNumber: get_one(group_attrs, "gidNumber"),
+        })
+    }
+}
@@ -1,10 +1,9 @@
-use lazy_static::lazy_static;
 use ldap3::SearchEntry;
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-use std::fmt::Debug;
-use std::str::FromStr;
+
+use super::attrs::{get_one, get_vec, require_one};
+use super::error::Error;
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
 #[allow(non_snake_case)]
@@ -21,58 +20,43 @@ pub struct LdapUser {
 }
 
 impl LdapUser {
-    #[must_use]
-    pub fn from_entry(entry: &SearchEntry) -> Self {
+    /// Builds an `LdapUser` from a raw search entry. `group_regex` matches a `memberOf`
+    /// DN under the configured group base and captures the bare group name (see
+    /// [`build_group_regex`]).
+    ///
+    /// Fails with [`Error::MissingAttribute`] if the entry lacks `cn`, `uid`, or
+    /// `krbPrincipalName`, rather than panicking.
+    pub fn try_from_entry(entry: &SearchEntry, group_regex: &Regex) -> Result<Self, Error> {
         let user_attrs = &entry.attrs;
-        LdapUser {
+        Ok(LdapUser {
             dn: entry.dn.clone(),
-            cn: get_one(user_attrs, "cn").unwrap(),
-            uid: get_one(user_attrs, "uid").unwrap(),
-            groups: get_groups(get_vec(user_attrs, "memberOf")),
-            krbPrincipalName: get_one(user_attrs, "krbPrincipalName").unwrap(),
+            cn: require_one(user_attrs, &entry.dn, "cn")?,
+            uid: require_one(user_attrs, &entry.dn, "uid")?,
+            groups: get_groups(get_vec(user_attrs, "memberOf"), group_regex),
+            krbPrincipalName: require_one(user_attrs, &entry.dn, "krbPrincipalName")?,
             mail: get_vec(user_attrs, "mail"),
             mobile: get_vec(user_attrs, "mobile"),
             ibutton: get_vec(user_attrs, "ibutton"),
             drinkBalance: get_one(user_attrs, "drinkBalance"),
-        }
-    }
-}
-
-fn get_one<T>(entry: &HashMap<String, Vec<String>>, field: &str) -> Option<T>
-where
-    T: FromStr,
-    <T as FromStr>::Err: Debug,
-{
-    match entry.get(field).map(|f| f.get(0).unwrap().parse::<T>()) {
-        Some(result) => match result {
-            Ok(r) => Some(r),
-            Err(_) => None,
-        },
-        None => None,
+        })
     }
 }
 
-fn get_vec<T>(entry: &HashMap<String, Vec<String>>, field: &str) -> Vec<T>
-where
-    T: FromStr,
-    <T as FromStr>::Err: Debug,
-{
-    match entry.get(field) {
-        Some(v) => v.iter().map(|f| f.parse::<T>().unwrap()).collect(),
-        None => vec![],
-    }
+/// Builds the regex used to parse a bare group name out of a `memberOf` DN nested
+/// under `group_base` (e.g. `cn=groups,cn=accounts,dc=csh,dc=rit,dc=edu`). Compile this
+/// once per `LdapClient` rather than per entry.
+#[must_use]
+pub fn build_group_regex(group_base: &str) -> Regex {
+    Regex::new(&format!(r"cn=(?P<name>\w+),{}", regex::escape(group_base)))
+        .expect("group_base should always produce a valid regex")
 }
 
 #[must_use]
-pub fn get_groups(member_of: Vec<String>) -> Vec<String> {
-    lazy_static! {
-        static ref GROUP_REGEX: Regex =
-            Regex::new(r"cn=(?P<name>\w+),cn=groups,cn=accounts,dc=csh,dc=rit,dc=edu").unwrap();
-    }
+pub fn get_groups(member_of: Vec<String>, group_regex: &Regex) -> Vec<String> {
     member_of
         .iter()
         .filter_map(|group| {
-            GROUP_REGEX
+            group_regex
                 .captures(group)
                 .map(|cap| cap["name"].to_owned())
         })